@@ -37,6 +37,42 @@ impl<'a> TryFrom<&mut Cursor<&'a [u8]>> for Opaque<'a, &'a [u8]> {
     }
 }
 
+impl<'a> Opaque<'a, &'a [u8]> {
+    /// Deserialises a new [`Opaque`] from `cursor`, bounds-checking the
+    /// declared length against both the remaining buffer and `max_len`
+    /// before slicing.
+    ///
+    /// Unlike [`Opaque::try_from`], this never panics on a truncated or
+    /// malicious declared length, and rejects declared lengths over
+    /// `max_len` up front rather than letting a caller pre-allocate based
+    /// on an attacker-controlled value. This is the entry point that should
+    /// be used to decode any untrusted/streamed input.
+    pub fn try_from_verified(
+        c: &mut Cursor<&'a [u8]>,
+        max_len: u32,
+    ) -> Result<Opaque<'a, &'a [u8]>, Error> {
+        let len = c.read_u32::<BigEndian>()?;
+        if len > max_len {
+            return Err(Error::InvalidLength);
+        }
+
+        let data = *c.get_ref();
+        let start = c.position() as usize;
+        let end = start.checked_add(len as usize).ok_or(Error::InvalidLength)?;
+        let padded_end = end as u32 + pad_length(len);
+
+        if end > data.len() || padded_end as usize > data.len() {
+            return Err(Error::InvalidLength);
+        }
+
+        c.set_position(padded_end as u64);
+        Ok(Opaque {
+            body: &data[start..end],
+            phantom: PhantomData,
+        })
+    }
+}
+
 impl<'a, T> Opaque<'a, T>
 where
     T: AsRef<[u8]> + Sized,
@@ -81,7 +117,7 @@ where
         let _ = buf.write_all(self.body.as_ref());
         let fill_bytes = pad_length(len);
         if fill_bytes > 0 {
-            buf.write_all(vec![0_u8; fill_bytes as usize].as_slice())?;
+            buf.write_all(&PAD[..fill_bytes as usize])?;
         }
         Ok(())
     }
@@ -93,13 +129,17 @@ where
     }
 }
 
+// A shared zero buffer used to write padding bytes, avoiding a fresh heap
+// allocation on every padded write.
+pub(crate) static PAD: [u8; 4] = [0; 4];
+
 // https://datatracker.ietf.org/doc/html/rfc1014#section-4
 // (5) Why must variable-length data be padded with zeros?
 // It is desirable that the same data encode into the same thing on all
 // machines, so that encoded data can be meaningfully compared or
 // checksummed.  Forcing the padded bytes to be zero ensures this.
 #[inline]
-fn pad_length(l: u32) -> u32 {
+pub(crate) fn pad_length(l: u32) -> u32 {
     if l % 4 == 0 {
         return 0;
     }
@@ -175,4 +215,27 @@ mod tests {
         // assert input == output
         assert!(buf.get_ref().iter().zip(raw.iter()).all(|(a, b)| a == b));
     }
+
+    #[test]
+    fn test_verified_opaque_within_limit() {
+        let raw = hex!("0000000c4c4150544f5151425044474d").as_slice();
+        let mut cursor = Cursor::new(raw);
+        let data = Opaque::try_from_verified(&mut cursor, 64).unwrap();
+        assert_eq!(data.as_ref().len(), 12);
+    }
+
+    #[test]
+    fn test_verified_opaque_rejects_declared_len_over_limit() {
+        let raw = hex!("0000000c4c4150544f5151425044474d").as_slice();
+        let mut cursor = Cursor::new(raw);
+        assert!(Opaque::try_from_verified(&mut cursor, 4).is_err());
+    }
+
+    #[test]
+    fn test_verified_opaque_rejects_truncated_buffer() {
+        // Declares a 12-byte body but only 4 bytes follow.
+        let raw = hex!("0000000c4c415054").as_slice();
+        let mut cursor = Cursor::new(raw);
+        assert!(Opaque::try_from_verified(&mut cursor, u32::MAX).is_err());
+    }
 }
@@ -0,0 +1,936 @@
+//! A [`serde`] data format for the XDR wire encoding used throughout this
+//! crate, so that RPC message structs can `#[derive(Serialize,
+//! Deserialize)]` instead of hand-writing [`crate::opaque::SerializeOpaque`]
+//! impls. All of the big-endian, 4-byte zero-padded rules are routed
+//! through [`pad_length`], exactly as [`Opaque`] already does.
+//!
+//! `Vec<u8>`/`&[u8]` fields serialised via `serde_bytes` (or wrapped in
+//! [`serde_bytes::Bytes`]/[`serde_bytes::ByteBuf`]) are special-cased to a
+//! single XDR variable-length opaque, rather than expanding to a 4-byte
+//! XDR int per byte the way a plain `Vec<u8>` sequence would. Fixed-length
+//! `[u8; N]` fields that must omit the length prefix should instead use
+//! `#[serde(with = "fixed_opaque")]`, see that module's docs.
+
+use std::io::{Cursor, Write};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use serde::{de, ser, Deserialize, Serialize};
+
+use crate::{
+    opaque::{pad_length, PAD},
+    Error,
+};
+
+// `ser::Error::custom` and `de::Error::custom` are both implemented below,
+// so an unqualified `custom_error(...)` call is ambiguous (E0034). Call
+// sites in this file go through this plain function instead, which both
+// trait impls delegate to.
+fn custom_error(msg: impl std::fmt::Display) -> Error {
+    Error::Message(msg.to_string())
+}
+
+impl ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        custom_error(msg)
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        custom_error(msg)
+    }
+}
+
+/// Serialises `value` as XDR into `writer`.
+pub fn to_writer<T, W>(value: &T, writer: &mut W) -> Result<(), Error>
+where
+    T: Serialize,
+    W: Write,
+{
+    value.serialize(&mut Serializer { writer })
+}
+
+/// Serialises `value` as XDR into a new `Vec<u8>`.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize,
+{
+    let mut buf = Vec::new();
+    to_writer(value, &mut buf)?;
+    Ok(buf)
+}
+
+/// Deserialises a `T` as XDR from `data`.
+pub fn from_slice<'de, T>(data: &'de [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    let mut de = Deserializer {
+        cursor: Cursor::new(data),
+    };
+    T::deserialize(&mut de)
+}
+
+/// Writes `len` followed by `bytes`, zero-padded to the next 4-byte
+/// boundary — the wire shape of XDR variable-length opaque data.
+fn write_variable_opaque<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), Error> {
+    let len = bytes.len() as u32;
+    writer.write_u32::<BigEndian>(len)?;
+    writer.write_all(bytes)?;
+    let fill_bytes = pad_length(len);
+    if fill_bytes > 0 {
+        writer.write_all(&PAD[..fill_bytes as usize])?;
+    }
+    Ok(())
+}
+
+/// Tuple-struct name used to route `#[serde(with = "fixed_opaque")]`
+/// fields through the raw, un-prefixed encode/decode path instead of the
+/// ordinary per-element tuple handling. Not a valid Rust identifier, so it
+/// can never collide with a real `#[derive(Serialize)]` struct name.
+const FIXED_OPAQUE_MARKER: &str = "$xdr::fixed_opaque";
+
+pub struct Serializer<'w, W: Write> {
+    writer: &'w mut W,
+}
+
+macro_rules! serialize_as_xdr_int {
+    ($method:ident, $t:ty, $write:ident) => {
+        fn $method(self, v: $t) -> Result<Self::Ok, Self::Error> {
+            Ok(self.writer.$write::<BigEndian>(v.into())?)
+        }
+    };
+}
+
+impl<'w, W: Write> ser::Serializer for &'w mut Serializer<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = TupleStructSerializer<'w, W>;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_u32::<BigEndian>(v as u32)?)
+    }
+
+    serialize_as_xdr_int!(serialize_i8, i8, write_i32);
+    serialize_as_xdr_int!(serialize_i16, i16, write_i32);
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_i32::<BigEndian>(v)?)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_i64::<BigEndian>(v)?)
+    }
+
+    serialize_as_xdr_int!(serialize_u8, u8, write_u32);
+    serialize_as_xdr_int!(serialize_u16, u16, write_u32);
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_u32::<BigEndian>(v)?)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_u64::<BigEndian>(v)?)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_f32::<BigEndian>(v)?)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(self.writer.write_f64::<BigEndian>(v)?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        write_variable_opaque(self.writer, v.as_bytes())
+    }
+
+    /// The bytes specialization: routes `Vec<u8>`/`&[u8]`/`serde_bytes`
+    /// fields to a single XDR variable-length opaque instead of a
+    /// per-element array.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        write_variable_opaque(self.writer, v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_bool(false)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        self.serialize_bool(true)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    /// Encodes as an XDR discriminated union: the variant index as the
+    /// 4-byte discriminant, followed by the arm's value.
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u32(variant_index)?;
+        value.serialize(self)
+    }
+
+    /// XDR variable-length array: a 4-byte element count followed by the
+    /// elements.
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        let len = len.ok_or_else(|| custom_error("sequence length must be known"))?;
+        self.serialize_u32(len as u32)?;
+        Ok(self)
+    }
+
+    /// XDR fixed-length array: no length prefix, just the elements.
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self)
+    }
+
+    /// Most tuple structs are treated like a plain XDR tuple (fields
+    /// back-to-back, no framing). The [`fixed_opaque`] marker is a special
+    /// case: its fields are raw bytes that must land on the wire as a
+    /// single, un-padded-per-byte run followed by one padding step, i.e.
+    /// RFC 4506 fixed-length opaque data rather than an XDR array of ints.
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        if name == FIXED_OPAQUE_MARKER {
+            Ok(TupleStructSerializer::FixedOpaque {
+                writer: &mut *self.writer,
+                bytes: Vec::new(),
+            })
+        } else {
+            Ok(TupleStructSerializer::Plain(&mut *self.writer))
+        }
+    }
+
+    /// XDR discriminated union arm with positional fields.
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(custom_error("XDR has no native map type"))
+    }
+
+    /// XDR `struct`: fields back-to-back in declaration order, with no
+    /// framing of their own.
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+
+    /// XDR discriminated union arm with named fields.
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+}
+
+macro_rules! impl_serialize_seq_like {
+    ($trait:ident, $method:ident) => {
+        impl<'w, W: Write> ser::$trait for &'w mut Serializer<'_, W> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<Self::Ok, Self::Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_serialize_seq_like!(SerializeSeq, serialize_element);
+impl_serialize_seq_like!(SerializeTuple, serialize_element);
+
+/// The [`ser::Serializer::SerializeTupleStruct`] state for
+/// [`Serializer`]: ordinary tuple structs serialize their fields through
+/// the plain writer, while the [`fixed_opaque`] marker buffers raw bytes
+/// so they can be written as one un-prefixed, once-padded run.
+pub enum TupleStructSerializer<'w, W: Write> {
+    Plain(&'w mut W),
+    FixedOpaque { writer: &'w mut W, bytes: Vec<u8> },
+}
+
+impl<'w, W: Write> ser::SerializeTupleStruct for TupleStructSerializer<'w, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        match self {
+            Self::Plain(writer) => value.serialize(&mut Serializer { writer: &mut **writer }),
+            Self::FixedOpaque { bytes, .. } => {
+                bytes.push(value.serialize(ByteSink)?);
+                Ok(())
+            }
+        }
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        match self {
+            Self::Plain(_) => Ok(()),
+            Self::FixedOpaque { writer, bytes } => {
+                writer.write_all(&bytes)?;
+                let fill_bytes = pad_length(bytes.len() as u32);
+                if fill_bytes > 0 {
+                    writer.write_all(&PAD[..fill_bytes as usize])?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Captures a single `u8` out of a `Serialize` value, used to pull raw
+/// bytes out of the per-field calls made while serializing a
+/// [`fixed_opaque`]-tagged `[u8; N]`.
+struct ByteSink;
+
+macro_rules! byte_sink_unsupported {
+    ($method:ident($($arg:ident: $ty:ty),*)) => {
+        fn $method(self, $($arg: $ty),*) -> Result<Self::Ok, Self::Error> {
+            Err(custom_error("fixed_opaque fields must be byte arrays"))
+        }
+    };
+}
+
+impl ser::Serializer for ByteSink {
+    type Ok = u8;
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<u8, Error>;
+    type SerializeTuple = ser::Impossible<u8, Error>;
+    type SerializeTupleStruct = ser::Impossible<u8, Error>;
+    type SerializeTupleVariant = ser::Impossible<u8, Error>;
+    type SerializeMap = ser::Impossible<u8, Error>;
+    type SerializeStruct = ser::Impossible<u8, Error>;
+    type SerializeStructVariant = ser::Impossible<u8, Error>;
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(v)
+    }
+
+    byte_sink_unsupported!(serialize_bool(_v: bool));
+    byte_sink_unsupported!(serialize_i8(_v: i8));
+    byte_sink_unsupported!(serialize_i16(_v: i16));
+    byte_sink_unsupported!(serialize_i32(_v: i32));
+    byte_sink_unsupported!(serialize_i64(_v: i64));
+    byte_sink_unsupported!(serialize_u16(_v: u16));
+    byte_sink_unsupported!(serialize_u32(_v: u32));
+    byte_sink_unsupported!(serialize_u64(_v: u64));
+    byte_sink_unsupported!(serialize_f32(_v: f32));
+    byte_sink_unsupported!(serialize_f64(_v: f64));
+    byte_sink_unsupported!(serialize_char(_v: char));
+    byte_sink_unsupported!(serialize_str(_v: &str));
+    byte_sink_unsupported!(serialize_bytes(_v: &[u8]));
+    byte_sink_unsupported!(serialize_unit());
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(custom_error("fixed_opaque fields must be byte arrays"))
+    }
+}
+
+impl<W: Write> ser::SerializeTupleVariant for &mut Serializer<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeMap for &mut Serializer<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, _key: &T) -> Result<(), Self::Error> {
+        Err(custom_error("XDR has no native map type"))
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, _value: &T) -> Result<(), Self::Error> {
+        Err(custom_error("XDR has no native map type"))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStruct for &mut Serializer<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeStructVariant for &mut Serializer<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+pub struct Deserializer<'de> {
+    cursor: Cursor<&'de [u8]>,
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    /// XDR is not self-describing, so every type must be requested
+    /// explicitly; there is no `deserialize_any`.
+    fn deserialize_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(custom_error(
+            "XDR is not self-describing; deserialize_any is unsupported",
+        ))
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_bool(self.cursor.read_u32::<BigEndian>()? != 0)
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i8(self.cursor.read_i32::<BigEndian>()? as i8)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i16(self.cursor.read_i32::<BigEndian>()? as i16)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i32(self.cursor.read_i32::<BigEndian>()?)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i64(self.cursor.read_i64::<BigEndian>()?)
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u8(self.cursor.read_u32::<BigEndian>()? as u8)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u16(self.cursor.read_u32::<BigEndian>()? as u16)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u32(self.cursor.read_u32::<BigEndian>()?)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u64(self.cursor.read_u64::<BigEndian>()?)
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f32(self.cursor.read_f32::<BigEndian>()?)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f64(self.cursor.read_f64::<BigEndian>()?)
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let v = self.cursor.read_u32::<BigEndian>()?;
+        let c = char::from_u32(v).ok_or_else(|| custom_error("invalid char discriminant"))?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let bytes = self.read_variable_opaque()?;
+        let s = String::from_utf8(bytes).map_err(|_| Error::InvalidString)?;
+        visitor.visit_string(s)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_byte_buf(self.read_variable_opaque()?)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.cursor.read_u32::<BigEndian>()? != 0 {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = self.cursor.read_u32::<BigEndian>()? as usize;
+        visitor.visit_seq(BoundedSeq {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BoundedSeq {
+            de: self,
+            remaining: len,
+        })
+    }
+
+    /// The [`fixed_opaque`] marker reads `len` raw bytes with no length
+    /// prefix (RFC 4506 fixed-length opaque data); any other tuple struct
+    /// is treated like a plain XDR tuple.
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if name == FIXED_OPAQUE_MARKER {
+            visitor.visit_byte_buf(self.read_fixed_opaque(len)?)
+        } else {
+            self.deserialize_tuple(len, visitor)
+        }
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(custom_error("XDR has no native map type"))
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(BoundedSeq {
+            de: self,
+            remaining: fields.len(),
+        })
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(custom_error("XDR cannot skip fields of unknown type"))
+    }
+}
+
+impl<'de> Deserializer<'de> {
+    fn read_variable_opaque(&mut self) -> Result<Vec<u8>, Error> {
+        let len = self.cursor.read_u32::<BigEndian>()?;
+        let data = *self.cursor.get_ref();
+        let start = self.cursor.position() as usize;
+        let end = start.checked_add(len as usize).ok_or(Error::InvalidLength)?;
+        let padded_end = end
+            .checked_add(pad_length(len) as usize)
+            .ok_or(Error::InvalidLength)?;
+        if padded_end > data.len() {
+            return Err(Error::InvalidLength);
+        }
+
+        let bytes = data[start..end].to_vec();
+        self.cursor.set_position(padded_end as u64);
+        Ok(bytes)
+    }
+
+    /// Reads exactly `len` raw bytes with no length prefix, consuming the
+    /// XDR padding that follows — the wire shape of fixed-length opaque
+    /// data.
+    fn read_fixed_opaque(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        let data = *self.cursor.get_ref();
+        let start = self.cursor.position() as usize;
+        let end = start.checked_add(len).ok_or(Error::InvalidLength)?;
+        let padded_end = end
+            .checked_add(pad_length(len as u32) as usize)
+            .ok_or(Error::InvalidLength)?;
+        if padded_end > data.len() {
+            return Err(Error::InvalidLength);
+        }
+
+        let bytes = data[start..end].to_vec();
+        self.cursor.set_position(padded_end as u64);
+        Ok(bytes)
+    }
+}
+
+struct BoundedSeq<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for BoundedSeq<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let discriminant = self.cursor.read_u32::<BigEndian>()?;
+        let value = seed.deserialize(de::value::U32Deserializer::<Error>::new(discriminant))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+/// `#[serde(with = "fixed_opaque")]` helper for `[u8; N]` fields that must
+/// be encoded as XDR fixed-length opaque data (no length prefix), as
+/// opposed to the variable-length opaque produced by `Vec<u8>`/
+/// `serde_bytes`. Routed through [`Serializer`]/[`Deserializer`]'s
+/// [`FIXED_OPAQUE_MARKER`] tuple-struct special case, since a length
+/// prefix can only be safely omitted when the field's size is known from
+/// its type rather than the wire.
+pub mod fixed_opaque {
+    use std::fmt;
+
+    use serde::{
+        de::{self, Visitor},
+        ser::SerializeTupleStruct,
+        Deserializer, Serializer,
+    };
+
+    use super::FIXED_OPAQUE_MARKER;
+
+    pub fn serialize<S, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_tuple_struct(FIXED_OPAQUE_MARKER, N)?;
+        for byte in bytes {
+            state.serialize_field(byte)?;
+        }
+        state.end()
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple_struct(FIXED_OPAQUE_MARKER, N, FixedBytesVisitor::<N>)
+    }
+
+    struct FixedBytesVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for FixedBytesVisitor<N> {
+        type Value = [u8; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{N} bytes of fixed-length XDR opaque data")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            self.visit_bytes(&v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Greeting {
+        id: u32,
+        #[serde(with = "serde_bytes")]
+        message: Vec<u8>,
+    }
+
+    #[test]
+    fn test_roundtrip_struct_with_bytes_field() {
+        let value = Greeting {
+            id: 7,
+            message: b"hi".to_vec(),
+        };
+
+        let buf = to_vec(&value).unwrap();
+        // 4-byte id + (4-byte len + 2 bytes + 2 pad bytes)
+        assert_eq!(buf.len(), 12);
+
+        let decoded: Greeting = from_slice(&buf).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn test_roundtrip_fixed_opaque() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct WithFixed {
+            #[serde(with = "fixed_opaque")]
+            tag: [u8; 4],
+        }
+
+        let value = WithFixed { tag: *b"ABCD" };
+        let buf = to_vec(&value).unwrap();
+        // No length prefix and already 4-byte aligned: no padding either.
+        assert_eq!(buf, b"ABCD");
+        assert_eq!(decoded_roundtrip::<WithFixed>(&buf), value);
+    }
+
+    fn decoded_roundtrip<T: for<'de> Deserialize<'de>>(buf: &[u8]) -> T {
+        from_slice(buf).unwrap()
+    }
+}
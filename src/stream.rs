@@ -0,0 +1,85 @@
+use std::io::{BufWriter, Write};
+
+use crate::opaque::SerializeOpaque;
+
+/// Default capacity of the internal buffer, chosen to comfortably hold a
+/// handful of RPC messages before a flush is forced.
+const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+/// A streaming XDR encoder that writes fields directly into a fixed-size
+/// buffered writer, rather than accumulating an entire message in a
+/// `Vec<u8>` first.
+///
+/// This is intended for large or multi-field messages (e.g. an NFS-style
+/// write payload) where building the whole message in memory before
+/// sending it would be wasteful. Writes are batched into the internal
+/// buffer and only flushed to the underlying writer (a socket, file, etc.)
+/// once that buffer fills, keeping the per-field overhead of each
+/// `write_all`/bounds check low.
+pub struct XdrStreamEncoder<W: Write> {
+    inner: BufWriter<W>,
+}
+
+impl<W: Write> XdrStreamEncoder<W> {
+    /// Wraps `writer` with the default buffer capacity.
+    pub fn new(writer: W) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, writer)
+    }
+
+    /// Wraps `writer` with an internal buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        Self {
+            inner: BufWriter::with_capacity(capacity, writer),
+        }
+    }
+
+    /// Writes an opaque field directly into the stream.
+    pub fn write_opaque<O: SerializeOpaque>(&mut self, opaque: &O) -> Result<(), std::io::Error> {
+        opaque.serialise_into(&mut self.inner)
+    }
+
+    /// Flushes any buffered bytes to the underlying writer.
+    pub fn flush(&mut self) -> Result<(), std::io::Error> {
+        self.inner.flush()
+    }
+
+    /// Consumes the encoder, flushing the buffer and returning the
+    /// underlying writer.
+    pub fn into_inner(mut self) -> Result<W, std::io::Error> {
+        self.flush()?;
+        self.inner
+            .into_inner()
+            .map_err(|err| err.into_error())
+    }
+}
+
+impl<W: Write> Write for XdrStreamEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::opaque::Opaque;
+
+    use super::*;
+
+    #[test]
+    fn test_streaming_encoder_flushes_buffered_opaque() {
+        let opaque = Opaque::from(vec![1_u8, 2, 3, 4, 5]);
+        let mut encoder = XdrStreamEncoder::with_capacity(8, Cursor::new(Vec::new()));
+
+        encoder.write_opaque(&opaque).unwrap();
+        let cursor = encoder.into_inner().unwrap();
+
+        // 4-byte length prefix + 5 bytes + 3 padding bytes
+        assert_eq!(cursor.into_inner().len(), 12);
+    }
+}
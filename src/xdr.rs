@@ -0,0 +1,533 @@
+use std::io::{Cursor, Write};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    opaque::{pad_length, PAD},
+    Error,
+};
+
+// Codec for the RFC 4506 primitive and composite types beyond variable-length
+// opaque: ints, bools, floats, strings, arrays, options, enums and unions.
+// `Opaque`/`SerializeOpaque` in the `opaque` module stay the entry point for
+// byte blobs; `pad_length`/`PAD` from there are reused by the string and
+// fixed-opaque impls below so padding stays in one place.
+// https://datatracker.ietf.org/doc/html/rfc4506
+
+/// Encodes `Self` into the XDR wire format.
+pub trait XdrEncode {
+    /// Writes the XDR encoding of `self` into `buf`.
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error>;
+
+    /// Returns the on-wire length of `self` once encoded.
+    fn encoded_len(&self) -> u32;
+}
+
+/// Decodes a `Self` from the XDR wire format.
+pub trait XdrDecode<'a>: Sized {
+    /// Reads an XDR-encoded `Self` from `cursor`.
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error>;
+}
+
+macro_rules! impl_xdr_int {
+    ($t:ty, $write:ident, $read:ident) => {
+        impl XdrEncode for $t {
+            fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+                buf.$write::<BigEndian>(*self)
+            }
+
+            fn encoded_len(&self) -> u32 {
+                std::mem::size_of::<$t>() as u32
+            }
+        }
+
+        impl<'a> XdrDecode<'a> for $t {
+            fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+                Ok(cursor.$read::<BigEndian>()?)
+            }
+        }
+    };
+}
+
+impl_xdr_int!(i32, write_i32, read_i32);
+impl_xdr_int!(u32, write_u32, read_u32);
+impl_xdr_int!(i64, write_i64, read_i64);
+impl_xdr_int!(u64, write_u64, read_u64);
+impl_xdr_int!(f32, write_f32, read_f32);
+impl_xdr_int!(f64, write_f64, read_f64);
+
+impl XdrEncode for bool {
+    /// Encodes as the 4-byte XDR `bool` (an `int` of 0 or 1).
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+        buf.write_u32::<BigEndian>(*self as u32)
+    }
+
+    fn encoded_len(&self) -> u32 {
+        4
+    }
+}
+
+impl<'a> XdrDecode<'a> for bool {
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+        Ok(cursor.read_u32::<BigEndian>()? != 0)
+    }
+}
+
+impl XdrEncode for String {
+    /// Encodes as an XDR string: a 4-byte length prefix, the UTF-8 bytes,
+    /// and zero padding to the next 4-byte boundary.
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+        let len = self.len() as u32;
+        buf.write_u32::<BigEndian>(len)?;
+        buf.write_all(self.as_bytes())?;
+        let fill_bytes = pad_length(len);
+        if fill_bytes > 0 {
+            buf.write_all(&PAD[..fill_bytes as usize])?;
+        }
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> u32 {
+        let len = self.len() as u32;
+        4 + len + pad_length(len)
+    }
+}
+
+impl<'a> XdrDecode<'a> for String {
+    /// Decodes an XDR string, validating that the payload is UTF-8.
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+        let len = cursor.read_u32::<BigEndian>()?;
+        let data = *cursor.get_ref();
+        let start = cursor.position() as usize;
+        let end = start.checked_add(len as usize).ok_or(Error::InvalidLength)?;
+        let padded_end = end
+            .checked_add(pad_length(len) as usize)
+            .ok_or(Error::InvalidLength)?;
+
+        if padded_end > data.len() {
+            return Err(Error::InvalidLength);
+        }
+        let bytes = data.get(start..end).ok_or(Error::InvalidLength)?;
+        let s = std::str::from_utf8(bytes)
+            .map_err(|_| Error::InvalidString)?
+            .to_owned();
+
+        cursor.set_position(padded_end as u64);
+        Ok(s)
+    }
+}
+
+impl<T, const N: usize> XdrEncode for [T; N]
+where
+    T: XdrEncode,
+{
+    /// Encodes a fixed-length XDR array: each element back-to-back, with no
+    /// length prefix (the length is part of the type).
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+        for elem in self {
+            elem.encode_into(buf)?;
+        }
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> u32 {
+        self.iter().map(XdrEncode::encoded_len).sum()
+    }
+}
+
+impl<'a, T, const N: usize> XdrDecode<'a> for [T; N]
+where
+    T: XdrDecode<'a>,
+{
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+        let mut items = Vec::with_capacity(N);
+        for _ in 0..N {
+            items.push(T::decode_from(cursor)?);
+        }
+        items
+            .try_into()
+            .map_err(|_| Error::InvalidLength)
+    }
+}
+
+// `u8` itself has no `XdrEncode`/`XdrDecode` impl — XDR has no native
+// single-byte type, and treating `[u8; N]` as an array of individually
+// padded 4-byte ints would be wrong. `[u8; N]` therefore gets its own
+// fixed-length opaque impl below instead of going through the generic
+// `[T; N]` impls above (`u8: XdrEncode`/`XdrDecode` is never satisfied, so
+// there's no overlap between the two).
+impl<const N: usize> XdrEncode for [u8; N] {
+    /// Encodes as RFC 4506 fixed-length opaque data: the raw bytes,
+    /// zero-padded to the next 4-byte boundary. Unlike variable-length
+    /// opaque, there is no length prefix — the length is fixed by the
+    /// type.
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+        buf.write_all(self)?;
+        let fill_bytes = pad_length(N as u32);
+        if fill_bytes > 0 {
+            buf.write_all(&PAD[..fill_bytes as usize])?;
+        }
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> u32 {
+        N as u32 + pad_length(N as u32)
+    }
+}
+
+impl<'a, const N: usize> XdrDecode<'a> for [u8; N] {
+    /// Decodes RFC 4506 fixed-length opaque data: `N` raw bytes, consuming
+    /// the padding that follows them.
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+        let data = *cursor.get_ref();
+        let start = cursor.position() as usize;
+        let end = start.checked_add(N).ok_or(Error::InvalidLength)?;
+        let padded_end = end
+            .checked_add(pad_length(N as u32) as usize)
+            .ok_or(Error::InvalidLength)?;
+
+        if padded_end > data.len() {
+            return Err(Error::InvalidLength);
+        }
+        let bytes = data.get(start..end).ok_or(Error::InvalidLength)?;
+        let array: [u8; N] = bytes.try_into().map_err(|_| Error::InvalidLength)?;
+
+        cursor.set_position(padded_end as u64);
+        Ok(array)
+    }
+}
+
+impl<T> XdrEncode for Vec<T>
+where
+    T: XdrEncode,
+{
+    /// Encodes a variable-length XDR array: a 4-byte element count followed
+    /// by each element back-to-back.
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+        buf.write_u32::<BigEndian>(self.len() as u32)?;
+        for elem in self {
+            elem.encode_into(buf)?;
+        }
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> u32 {
+        4 + self.iter().map(XdrEncode::encoded_len).sum::<u32>()
+    }
+}
+
+impl<'a, T> XdrDecode<'a> for Vec<T>
+where
+    T: XdrDecode<'a>,
+{
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+        let count = cursor.read_u32::<BigEndian>()?;
+        (0..count).map(|_| T::decode_from(cursor)).collect()
+    }
+}
+
+impl<T> XdrEncode for Option<T>
+where
+    T: XdrEncode,
+{
+    /// Encodes XDR optional ("pointer") data: a 4-byte bool discriminant
+    /// followed by the value, if present.
+    fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+        match self {
+            Some(v) => {
+                true.encode_into(buf)?;
+                v.encode_into(buf)
+            }
+            None => false.encode_into(buf),
+        }
+    }
+
+    fn encoded_len(&self) -> u32 {
+        4 + self.as_ref().map(XdrEncode::encoded_len).unwrap_or(0)
+    }
+}
+
+impl<'a, T> XdrDecode<'a> for Option<T>
+where
+    T: XdrDecode<'a>,
+{
+    fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+        if bool::decode_from(cursor)? {
+            Ok(Some(T::decode_from(cursor)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// An XDR `enum`: encoded on the wire as a plain 4-byte signed `int`
+/// discriminant (RFC 4506 §4.3).
+///
+/// There is deliberately no blanket `XdrEncode`/`XdrDecode` impl for
+/// `T: XdrEnum` (a type can only implement one or the other trait once,
+/// and a crate using both `XdrEnum` and [`XdrUnion`] would conflict).
+/// Implement `XdrEncode`/`XdrDecode` for your enum directly, delegating to
+/// [`encode_enum`]/[`decode_enum`]:
+///
+/// ```ignore
+/// impl XdrEncode for MyEnum {
+///     fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+///         encode_enum(self, buf)
+///     }
+///     fn encoded_len(&self) -> u32 { 4 }
+/// }
+/// ```
+pub trait XdrEnum: Copy + Sized {
+    /// Returns the wire discriminant for this variant.
+    fn to_discriminant(self) -> i32;
+
+    /// Recovers a variant from its wire discriminant.
+    fn from_discriminant(discriminant: i32) -> Result<Self, Error>;
+}
+
+/// Encodes `value`'s discriminant as the XDR wire form of an [`XdrEnum`].
+pub fn encode_enum<T: XdrEnum, W: Write>(value: &T, buf: &mut W) -> Result<(), std::io::Error> {
+    value.to_discriminant().encode_into(buf)
+}
+
+/// Decodes an [`XdrEnum`] from its XDR wire form.
+pub fn decode_enum<T: XdrEnum>(cursor: &mut Cursor<&[u8]>) -> Result<T, Error> {
+    T::from_discriminant(i32::decode_from(cursor)?)
+}
+
+/// An XDR discriminated union (RFC 4506 §4.15): a 4-byte discriminant
+/// selecting which arm follows.
+///
+/// As with [`XdrEnum`], there is no blanket `XdrEncode` impl here (it
+/// would conflict with one for `XdrEnum`); implement `XdrEncode` for your
+/// union directly, delegating to [`encode_union`]:
+///
+/// ```ignore
+/// impl XdrEncode for MyUnion {
+///     fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+///         encode_union(self, buf)
+///     }
+///     fn encoded_len(&self) -> u32 { 4 + self.arm_len() }
+/// }
+/// ```
+///
+/// Decoding a union requires dispatching on the discriminant to the right
+/// arm type, which is specific to each union, so no generic `decode_union`
+/// helper is provided; implement `XdrDecode` by hand, reading the
+/// discriminant with `i32::decode_from` and matching on it.
+pub trait XdrUnion {
+    /// Returns the 4-byte discriminant identifying the selected arm.
+    fn discriminant(&self) -> i32;
+
+    /// Encodes the body of the currently selected arm.
+    fn encode_arm<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error>;
+
+    /// Returns the on-wire length of the currently selected arm's body.
+    fn arm_len(&self) -> u32;
+}
+
+/// Encodes `value`'s discriminant followed by its arm, as the XDR wire
+/// form of an [`XdrUnion`].
+pub fn encode_union<T: XdrUnion, W: Write>(value: &T, buf: &mut W) -> Result<(), std::io::Error> {
+    value.discriminant().encode_into(buf)?;
+    value.encode_arm(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_ints() {
+        let mut buf = Vec::new();
+        42_i32.encode_into(&mut buf).unwrap();
+        assert_eq!(buf, hex!("0000002a"));
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(i32::decode_from(&mut cursor).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_roundtrip_bool() {
+        let mut buf = Vec::new();
+        true.encode_into(&mut buf).unwrap();
+        assert_eq!(buf, hex!("00000001"));
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert!(bool::decode_from(&mut cursor).unwrap());
+    }
+
+    #[test]
+    fn test_roundtrip_padded_string() {
+        let s = "foo".to_string();
+        let mut buf = Vec::new();
+        s.encode_into(&mut buf).unwrap();
+        // 4-byte length + 3 bytes + 1 pad byte
+        assert_eq!(buf.len(), 8);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(String::decode_from(&mut cursor).unwrap(), s);
+    }
+
+    #[test]
+    fn test_roundtrip_variable_array() {
+        let v: Vec<u32> = vec![1, 2, 3];
+        let mut buf = Vec::new();
+        v.encode_into(&mut buf).unwrap();
+        assert_eq!(buf.len(), v.encoded_len() as usize);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(Vec::<u32>::decode_from(&mut cursor).unwrap(), v);
+    }
+
+    #[test]
+    fn test_roundtrip_optional() {
+        let present: Option<u32> = Some(7);
+        let mut buf = Vec::new();
+        present.encode_into(&mut buf).unwrap();
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(Option::<u32>::decode_from(&mut cursor).unwrap(), present);
+
+        let absent: Option<u32> = None;
+        let mut buf = Vec::new();
+        absent.encode_into(&mut buf).unwrap();
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(Option::<u32>::decode_from(&mut cursor).unwrap(), absent);
+    }
+
+    #[test]
+    fn test_roundtrip_fixed_opaque() {
+        let payload: [u8; 5] = [1, 2, 3, 4, 5];
+        let mut buf = Vec::new();
+        payload.encode_into(&mut buf).unwrap();
+        // 5 bytes + 3 padding bytes
+        assert_eq!(buf.len(), 8);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(<[u8; 5]>::decode_from(&mut cursor).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_roundtrip_fixed_opaque_already_aligned() {
+        let payload: [u8; 4] = [1, 2, 3, 4];
+        let mut buf = Vec::new();
+        payload.encode_into(&mut buf).unwrap();
+        assert_eq!(buf.len(), 4);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(<[u8; 4]>::decode_from(&mut cursor).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_roundtrip_fixed_array_of_non_byte_elements() {
+        let v: [u32; 3] = [1, 2, 3];
+        let mut buf = Vec::new();
+        v.encode_into(&mut buf).unwrap();
+        assert_eq!(buf.len(), 12);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(<[u32; 3]>::decode_from(&mut cursor).unwrap(), v);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Proto {
+        Tcp,
+        Udp,
+    }
+
+    impl XdrEnum for Proto {
+        fn to_discriminant(self) -> i32 {
+            match self {
+                Proto::Tcp => 6,
+                Proto::Udp => 17,
+            }
+        }
+
+        fn from_discriminant(discriminant: i32) -> Result<Self, Error> {
+            match discriminant {
+                6 => Ok(Proto::Tcp),
+                17 => Ok(Proto::Udp),
+                _ => Err(Error::InvalidLength),
+            }
+        }
+    }
+
+    impl XdrEncode for Proto {
+        fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+            encode_enum(self, buf)
+        }
+
+        fn encoded_len(&self) -> u32 {
+            4
+        }
+    }
+
+    impl<'a> XdrDecode<'a> for Proto {
+        fn decode_from(cursor: &mut Cursor<&'a [u8]>) -> Result<Self, Error> {
+            decode_enum(cursor)
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_enum() {
+        let mut buf = Vec::new();
+        Proto::Udp.encode_into(&mut buf).unwrap();
+        assert_eq!(buf, hex!("00000011"));
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        assert_eq!(Proto::decode_from(&mut cursor).unwrap(), Proto::Udp);
+    }
+
+    enum Result_ {
+        Ok(u32),
+        Err,
+    }
+
+    impl XdrUnion for Result_ {
+        fn discriminant(&self) -> i32 {
+            match self {
+                Result_::Ok(_) => 0,
+                Result_::Err => 1,
+            }
+        }
+
+        fn encode_arm<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+            match self {
+                Result_::Ok(v) => v.encode_into(buf),
+                Result_::Err => Ok(()),
+            }
+        }
+
+        fn arm_len(&self) -> u32 {
+            match self {
+                Result_::Ok(v) => v.encoded_len(),
+                Result_::Err => 0,
+            }
+        }
+    }
+
+    impl XdrEncode for Result_ {
+        fn encode_into<W: Write>(&self, buf: &mut W) -> Result<(), std::io::Error> {
+            encode_union(self, buf)
+        }
+
+        fn encoded_len(&self) -> u32 {
+            4 + self.arm_len()
+        }
+    }
+
+    #[test]
+    fn test_encode_union() {
+        let mut buf = Vec::new();
+        Result_::Ok(42).encode_into(&mut buf).unwrap();
+        assert_eq!(buf, hex!("000000000000002a"));
+
+        let mut buf = Vec::new();
+        Result_::Err.encode_into(&mut buf).unwrap();
+        assert_eq!(buf, hex!("00000001"));
+    }
+}
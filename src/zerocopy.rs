@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{
+    opaque::{pad_length, Opaque},
+    Error,
+};
+
+/// A "validate once, then borrow" view over a buffer containing a
+/// back-to-back sequence of length-prefixed XDR opaque fields (e.g. a
+/// captured/mmap'd file of serialised RPC calls).
+///
+/// [`BulkOpaques::validate`] walks the buffer exactly once, checking that
+/// every declared length/pad boundary is consistent with the buffer and
+/// that `data`'s base pointer is 4-byte aligned (a precondition for the
+/// XDR wire format and typical of `mmap`-backed buffers). After that single
+/// pass, [`BulkOpaques::get`] hands back borrowed [`Opaque`] views in O(1)
+/// with no further bounds-checking or copying.
+pub struct BulkOpaques<'a> {
+    data: &'a [u8],
+    // Byte offsets of each field's body, i.e. `data[start..end]` is the
+    // unpadded payload of field `i`.
+    bounds: Vec<(usize, usize)>,
+    phantom: PhantomData<&'a [u8]>,
+}
+
+impl<'a> BulkOpaques<'a> {
+    /// Validates `data` as a back-to-back sequence of length-prefixed
+    /// opaque fields, filling the buffer from start to end, and returns a
+    /// view that can be indexed without further validation.
+    ///
+    /// Returns an error if `data`'s base pointer is not 4-byte aligned, or
+    /// if any declared length/pad boundary would run past the end of
+    /// `data`.
+    pub fn validate(data: &'a [u8]) -> Result<Self, Error> {
+        if !(data.as_ptr() as usize).is_multiple_of(4) {
+            return Err(Error::InvalidAlignment);
+        }
+
+        let mut bounds = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let header = data.get(offset..offset + 4).ok_or(Error::InvalidLength)?;
+            let len = BigEndian::read_u32(header) as usize;
+
+            let start = offset + 4;
+            let end = start.checked_add(len).ok_or(Error::InvalidLength)?;
+            let padded_end = end + pad_length(len as u32) as usize;
+
+            if padded_end > data.len() {
+                return Err(Error::InvalidLength);
+            }
+
+            bounds.push((start, end));
+            offset = padded_end;
+        }
+
+        Ok(Self {
+            data,
+            bounds,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the number of opaque fields found during validation.
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Returns the `i`th field as a borrowed [`Opaque`], without
+    /// re-validating its bounds.
+    pub fn get(&self, i: usize) -> Option<Opaque<'a, &'a [u8]>> {
+        let &(start, end) = self.bounds.get(i)?;
+        Some(Opaque::from(&self.data[start..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_and_borrow_sequence() {
+        // Two length-prefixed fields, back-to-back: "ab" (padded to 4) and
+        // "abcd" (already 4-byte aligned, no padding).
+        let raw = hex!("00000002616200000000000461626364").as_slice();
+        // Force the aligned scenario regardless of the test binary's own
+        // allocation: copy into a 4-byte aligned owned buffer.
+        let mut aligned = vec![0_u32; (raw.len() + 3) / 4];
+        let aligned_bytes =
+            unsafe { std::slice::from_raw_parts_mut(aligned.as_mut_ptr() as *mut u8, raw.len()) };
+        aligned_bytes.copy_from_slice(raw);
+
+        let parsed = BulkOpaques::validate(aligned_bytes).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.get(0).unwrap().as_ref(), b"ab");
+        assert_eq!(parsed.get(1).unwrap().as_ref(), b"abcd");
+        assert!(parsed.get(2).is_none());
+    }
+
+    #[test]
+    fn test_validate_rejects_truncated_sequence() {
+        let raw = hex!("000000ff6162").as_slice();
+        let mut aligned = vec![0_u32; (raw.len() + 3) / 4];
+        let aligned_bytes =
+            unsafe { std::slice::from_raw_parts_mut(aligned.as_mut_ptr() as *mut u8, raw.len()) };
+        aligned_bytes.copy_from_slice(raw);
+
+        assert!(BulkOpaques::validate(aligned_bytes).is_err());
+    }
+}